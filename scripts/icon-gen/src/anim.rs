@@ -0,0 +1,71 @@
+//! Assemble a sequence of identically-sized still frames into an animated
+//! APNG or GIF icon.
+//!
+//! The caller center-crops and resizes each frame the same way `save_image`
+//! does; this module only handles encoding once every frame has been
+//! cropped to the same size.
+
+use crate::pixelate::{apply_shared_palette_indexed, shared_palette};
+use image::RgbaImage;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// Encode `frames` as an infinitely-looping GIF at `path`.
+///
+/// Builds one palette histogram across every frame (via
+/// [`pixelate::shared_palette`](crate::pixelate::shared_palette)) instead of
+/// quantizing each frame independently, so colors stay stable between frames.
+pub fn encode_gif(frames: &[RgbaImage], delay_ms: u16, palette_size: usize, path: &Path) -> Result<(), Box<dyn Error>> {
+    let (width, height) = frames[0].dimensions();
+
+    // GIF's transparent index is a u8 past the end of the palette, so the
+    // palette itself can have at most 255 entries; `shared_palette` also
+    // clamps to this, but an explicit clamp here means the bound holds even
+    // if a future caller quantizes the palette itself before reaching it.
+    let palette = shared_palette(frames, palette_size.min(255));
+
+    let mut rgb_palette = Vec::with_capacity((palette.len() + 1) * 3);
+    for [r, g, b, _] in &palette {
+        rgb_palette.extend_from_slice(&[*r, *g, *b]);
+    }
+    rgb_palette.extend_from_slice(&[0, 0, 0]); // reserved transparent index
+
+    let file = File::create(path)?;
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), width as u16, height as u16, &rgb_palette)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for frame in frames {
+        let (indices, transparent_index) = apply_shared_palette_indexed(frame, &palette);
+        let mut gif_frame = gif::Frame::from_indexed_pixels(width as u16, height as u16, indices, None);
+        gif_frame.delay = delay_ms / 10; // GIF delay is hundredths of a second
+        gif_frame.transparent = Some(transparent_index);
+        encoder.write_frame(&gif_frame)?;
+    }
+
+    Ok(())
+}
+
+/// Encode `frames` as a full-RGBA, infinitely-looping APNG at `path`, played
+/// back at `fps`.
+pub fn encode_apng(frames: &[RgbaImage], fps: u32, path: &Path) -> Result<(), Box<dyn Error>> {
+    let (width, height) = frames[0].dimensions();
+
+    let file = File::create(path)?;
+    let writer = BufWriter::new(file);
+
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0)?; // 0 plays = loop forever
+    encoder.set_frame_delay(1, fps as u16)?;
+
+    let mut writer = encoder.write_header()?;
+    for frame in frames {
+        writer.write_image_data(frame.as_raw())?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
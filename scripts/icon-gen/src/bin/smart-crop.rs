@@ -1,20 +1,44 @@
 //! Smart crop the icon using Gemini vision to find the cat.
 //!
 //! Analyzes the current icon, finds the cat's bounding box, and crops tighter.
+//! Supports a manual `--crop` override and an `--interactive` confirm/retry
+//! loop for when Gemini's proposed box isn't right.
 //!
 //! # Usage
 //!
 //! ```bash
 //! GEMINI_API_KEY=your_key cargo run --bin smart-crop
+//! # skip Gemini entirely with a known-good box:
+//! cargo run --bin smart-crop -- --crop 150,80,620,550
+//! # confirm/retry/correct the box before anything is written:
+//! GEMINI_API_KEY=your_key cargo run --bin smart-crop -- --interactive
+//! # terminal preview / palette report / clipboard copy of the result:
+//! cargo run --bin smart-crop -- --crop 150,80,620,550 --preview --info --copy
 //! ```
 
+use icon_gen::clipboard;
+use icon_gen::preview;
+use icon_gen::report;
 use image::imageops::FilterType;
 use image::ImageFormat;
 use rust_genai::{Client, InteractionStatus};
 use std::env;
-use std::io::Cursor;
+use std::io::{self, Cursor, Write};
 use std::path::PathBuf;
 
+type BBox = (u32, u32, u32, u32);
+
+const GEMINI_PROMPT: &str = r#"Look at this image and find the cat (including the yarn ball it's playing with).
+
+Return ONLY the bounding box coordinates as four integers separated by commas: left,top,right,bottom
+
+The coordinates should be pixel values relative to the image dimensions.
+Include some padding around the cat (about 5-10% of the crop size).
+
+Example response: 150,80,620,550
+
+Do not include any other text, just the four numbers."#;
+
 fn get_assets_dir() -> PathBuf {
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
     PathBuf::from(manifest_dir)
@@ -25,40 +49,34 @@ fn get_assets_dir() -> PathBuf {
         .join("assets")
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let api_key = env::var("GEMINI_API_KEY").expect("GEMINI_API_KEY environment variable not set");
-    let client = Client::builder(api_key).build();
-
-    let assets_dir = get_assets_dir();
-    let icon_path = assets_dir.join("icon.png");
-
-    println!("=== SMART CROP ===\n");
-    println!("Loading: {}\n", icon_path.display());
-
-    // Load current icon
-    let icon_bytes = std::fs::read(&icon_path)?;
-
-    // Ask Gemini for bounding box
-    println!("Analyzing image to find cat bounds...\n");
-
-    let prompt = r#"Look at this image and find the cat (including the yarn ball it's playing with).
-
-Return ONLY the bounding box coordinates as four integers separated by commas: left,top,right,bottom
-
-The coordinates should be pixel values relative to the image dimensions.
-Include some padding around the cat (about 5-10% of the crop size).
-
-Example response: 150,80,620,550
+/// Parse a `--crop left,top,right,bottom` value.
+fn parse_crop_arg(s: &str) -> Option<BBox> {
+    let coords: Vec<u32> = s.split(',').filter_map(|p| p.trim().parse().ok()).collect();
+    match coords.as_slice() {
+        [left, top, right, bottom] => Some((*left, *top, *right, *bottom)),
+        _ => None,
+    }
+}
 
-Do not include any other text, just the four numbers."#;
+/// Clamp `right`/`bottom` to the image dimensions and reject degenerate
+/// boxes, instead of only checking that four integers parsed.
+fn validate_box((left, top, right, bottom): BBox, width: u32, height: u32) -> Result<BBox, String> {
+    let right = right.min(width);
+    let bottom = bottom.min(height);
+    if right <= left || bottom <= top {
+        return Err(format!(
+            "crop box ({left},{top},{right},{bottom}) is degenerate for a {width}x{height} image"
+        ));
+    }
+    Ok((left, top, right, bottom))
+}
 
-    // Use new DX helper - no manual base64 encoding needed!
+async fn ask_gemini_for_box(client: &Client, icon_bytes: &[u8]) -> Result<BBox, Box<dyn std::error::Error>> {
     let response = client
         .interaction()
         .with_model("gemini-3-flash-preview")
-        .with_text(prompt)
-        .add_image_bytes(&icon_bytes, "image/png")
+        .with_text(GEMINI_PROMPT)
+        .add_image_bytes(icon_bytes, "image/png")
         .create()
         .await?;
 
@@ -69,24 +87,105 @@ Do not include any other text, just the four numbers."#;
     let text = response.text().ok_or("No text response")?;
     println!("Gemini response: {}\n", text);
 
-    // Parse bounding box
-    let coords: Vec<u32> = text
-        .trim()
-        .split(',')
-        .filter_map(|s| s.trim().parse().ok())
-        .collect();
+    parse_crop_arg(text.trim()).ok_or_else(|| format!("Expected 4 coordinates, got: {:?}", text).into())
+}
 
-    if coords.len() != 4 {
-        return Err(format!("Expected 4 coordinates, got: {:?}", coords).into());
+/// Render a quick preview of `bbox` cropped from `img` so the user can judge
+/// it before accepting, re-prompting, or typing a correction.
+fn preview_box(img: &image::DynamicImage, bbox: BBox) {
+    let (left, top, right, bottom) = bbox;
+    let cropped = img.crop_imm(left, top, right - left, bottom - top);
+    let mut buf = Cursor::new(Vec::new());
+    if cropped.write_to(&mut buf, ImageFormat::Png).is_ok() && !preview::preview_png(&buf.into_inner()) {
+        println!("(terminal preview unsupported)");
     }
+}
 
-    let (left, top, right, bottom) = (coords[0], coords[1], coords[2], coords[3]);
-    println!("Bounding box: left={}, top={}, right={}, bottom={}", left, top, right, bottom);
+fn prompt_line(message: &str) -> io::Result<String> {
+    print!("{}", message);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+/// Print the proposed box and a preview, then ask the user to accept it,
+/// re-prompt Gemini, or type a corrected box. Loops until accepted.
+async fn confirm_box(
+    client: &Client,
+    icon_bytes: &[u8],
+    img: &image::DynamicImage,
+    mut bbox: BBox,
+) -> Result<BBox, Box<dyn std::error::Error>> {
+    loop {
+        println!(
+            "Proposed box: left={}, top={}, right={}, bottom={}",
+            bbox.0, bbox.1, bbox.2, bbox.3
+        );
+        preview_box(img, bbox);
+
+        let answer = prompt_line("[a]ccept / [r]etry / or type a corrected \"left,top,right,bottom\": ")?;
+        match answer.to_lowercase().as_str() {
+            "a" | "accept" | "" => return Ok(bbox),
+            "r" | "retry" => {
+                let proposed = ask_gemini_for_box(client, icon_bytes).await?;
+                bbox = validate_box(proposed, img.width(), img.height())?;
+            }
+            other => match parse_crop_arg(other) {
+                Some(corrected) => bbox = validate_box(corrected, img.width(), img.height())?,
+                None => println!("Couldn't parse that as left,top,right,bottom, try again.\n"),
+            },
+        }
+    }
+}
 
-    // Load image and crop
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+    let preview = args.iter().any(|a| a == "--preview");
+    let interactive = args.iter().any(|a| a == "--interactive");
+    let info = args.iter().any(|a| a == "--info");
+    let copy = args.iter().any(|a| a == "--copy");
+    let manual_crop = match args.iter().position(|a| a == "--crop").and_then(|i| args.get(i + 1)) {
+        Some(s) => Some(parse_crop_arg(s).ok_or_else(|| format!("--crop expects left,top,right,bottom, got: {s}"))?),
+        None => None,
+    };
+
+    let assets_dir = get_assets_dir();
+    let icon_path = assets_dir.join("icon.png");
+
+    println!("=== SMART CROP ===\n");
+    println!("Loading: {}\n", icon_path.display());
+
+    let icon_bytes = std::fs::read(&icon_path)?;
     let img = image::load_from_memory(&icon_bytes)?;
     println!("Original size: {}x{}", img.width(), img.height());
 
+    let client = if manual_crop.is_none() {
+        let api_key = env::var("GEMINI_API_KEY").expect("GEMINI_API_KEY environment variable not set");
+        Some(Client::builder(api_key).build())
+    } else {
+        None
+    };
+
+    let bbox = match manual_crop {
+        Some(bbox) => validate_box(bbox, img.width(), img.height())?,
+        None => {
+            let client = client.as_ref().unwrap();
+            println!("Analyzing image to find cat bounds...\n");
+            let proposed = ask_gemini_for_box(client, &icon_bytes).await?;
+            let proposed = validate_box(proposed, img.width(), img.height())?;
+            if interactive {
+                confirm_box(client, &icon_bytes, &img, proposed).await?
+            } else {
+                proposed
+            }
+        }
+    };
+
+    let (left, top, right, bottom) = bbox;
+    println!("Bounding box: left={}, top={}, right={}, bottom={}", left, top, right, bottom);
+
     // Make it square (use the larger dimension)
     let crop_width = right - left;
     let crop_height = bottom - top;
@@ -104,6 +203,10 @@ Do not include any other text, just the four numbers."#;
 
     let cropped = img.crop_imm(crop_left, crop_top, size, size);
 
+    if info {
+        report::print_palette_report(&cropped.to_rgba8());
+    }
+
     // Save at different sizes
     for target_size in [512u32, 1024u32] {
         let resized = cropped.resize(target_size, target_size, FilterType::Lanczos3);
@@ -111,9 +214,14 @@ Do not include any other text, just the four numbers."#;
 
         let mut buf = Cursor::new(Vec::new());
         resized.write_to(&mut buf, ImageFormat::Png)?;
-        std::fs::write(&path, buf.into_inner())?;
+        let png_bytes = buf.into_inner();
+        std::fs::write(&path, &png_bytes)?;
 
         println!("Saved: {}", path.display());
+
+        if preview && target_size == 512 && !preview::preview_png(&png_bytes) {
+            println!("(terminal preview unsupported, open {} to view)", path.display());
+        }
     }
 
     // Save cropped original
@@ -123,9 +231,53 @@ Do not include any other text, just the four numbers."#;
     std::fs::write(&path, buf.into_inner())?;
     println!("Saved: {}", path.display());
 
+    if copy {
+        clipboard::copy_to_clipboard(&cropped.to_rgba8())?;
+        println!("Copied {} to clipboard", path.display());
+    }
+
     println!("\nDone! Test with:");
     println!("  EVENT_BUS_ICON={}/icon-512.png terminal-notifier -title Test -message Hi -sender com.apple.Terminal -appIcon {}/icon-512.png",
              assets_dir.display(), assets_dir.display());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_box_passes_through_in_bounds_box() {
+        assert_eq!(validate_box((10, 20, 100, 200), 640, 480), Ok((10, 20, 100, 200)));
+    }
+
+    #[test]
+    fn validate_box_clamps_right_and_bottom_to_image_size() {
+        assert_eq!(validate_box((10, 20, 900, 900), 640, 480), Ok((10, 20, 640, 480)));
+    }
+
+    #[test]
+    fn validate_box_rejects_degenerate_box() {
+        assert!(validate_box((100, 20, 100, 200), 640, 480).is_err());
+        assert!(validate_box((10, 200, 100, 200), 640, 480).is_err());
+    }
+
+    #[test]
+    fn validate_box_rejects_box_that_clamps_to_degenerate() {
+        // right/bottom clamp down to width/height, which can collapse an
+        // otherwise-valid-looking box onto left/top.
+        assert!(validate_box((640, 20, 900, 200), 640, 480).is_err());
+    }
+
+    #[test]
+    fn parse_crop_arg_accepts_four_comma_separated_integers() {
+        assert_eq!(parse_crop_arg("150,80,620,550"), Some((150, 80, 620, 550)));
+    }
+
+    #[test]
+    fn parse_crop_arg_rejects_wrong_count_or_non_numeric() {
+        assert_eq!(parse_crop_arg("150,80,620"), None);
+        assert_eq!(parse_crop_arg("a,b,c,d"), None);
+    }
+}
@@ -0,0 +1,19 @@
+//! Copy a generated icon directly onto the system clipboard as image data,
+//! so it can be pasted straight into chat or an issue tracker instead of
+//! only ever being written to `assets/`.
+
+use arboard::{Clipboard, ImageData};
+use image::RgbaImage;
+use std::borrow::Cow;
+use std::error::Error;
+
+/// Place `img`'s raw RGBA pixels on the system clipboard.
+pub fn copy_to_clipboard(img: &RgbaImage) -> Result<(), Box<dyn Error>> {
+    let mut clipboard = Clipboard::new()?;
+    clipboard.set_image(ImageData {
+        width: img.width() as usize,
+        height: img.height() as usize,
+        bytes: Cow::Borrowed(img.as_raw()),
+    })?;
+    Ok(())
+}
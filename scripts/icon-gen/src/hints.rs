@@ -0,0 +1,58 @@
+//! Notification presentation metadata: how an event's urgency and category
+//! map to an icon variant.
+//!
+//! This models the data a dispatch layer needs to pick an icon; nothing in
+//! this module maps concrete event kinds to a `NotificationHints` value or
+//! performs the dispatch lookup itself.
+//!
+//! Scope note: that core crate isn't in this tree, so nothing here picks a
+//! `NotificationHints` value automatically from a live event. `icon-gen`'s
+//! `--urgency`/`--category` flags are a manual stand-in that let a person
+//! reproduce one icon variant at a time; automatic, event-driven selection
+//! is follow-up work against the event-bus core crate, not something this
+//! module can deliver on its own.
+
+use std::collections::HashMap;
+
+/// How urgently a notification should be surfaced to the user.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Urgency {
+    Low,
+    Normal,
+    Critical,
+}
+
+/// Structured presentation metadata attached to an event.
+#[derive(Clone, Debug)]
+pub struct NotificationHints {
+    pub urgency: Urgency,
+    pub category: String,
+    pub hints: HashMap<String, String>,
+}
+
+impl NotificationHints {
+    pub fn new(urgency: Urgency, category: impl Into<String>) -> Self {
+        Self {
+            urgency,
+            category: category.into(),
+            hints: HashMap::new(),
+        }
+    }
+
+    pub fn with_hint(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.hints.insert(key.into(), value.into());
+        self
+    }
+
+    /// Name of the icon variant (matching the `icon-<variant>-*.png` files
+    /// produced by `--icon-set`) this notification should display.
+    pub fn icon_variant(&self) -> &'static str {
+        if self.urgency == Urgency::Critical {
+            "critical"
+        } else if self.category == "success" {
+            "success"
+        } else {
+            "normal"
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! Shared image-processing helpers for the icon-gen binaries.
+
+pub mod anim;
+pub mod clipboard;
+pub mod hints;
+pub mod pixelate;
+pub mod preview;
+pub mod report;
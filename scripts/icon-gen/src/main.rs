@@ -8,10 +8,34 @@
 //! GEMINI_API_KEY=your_key cargo run --release
 //! # or with custom prompt:
 //! GEMINI_API_KEY=your_key cargo run --release -- "your custom prompt"
+//!
+//! # pixel-art quantization:
+//! cargo run --release -- --grid 48 --palette-size 16
+//! cargo run --release -- --no-pixelate
+//!
+//! # terminal preview / palette report / clipboard copy:
+//! cargo run --release -- --preview --info --copy
+//!
+//! # notification hints: picks the variant prompt and output filename (icon, icon-critical, icon-success)
+//! cargo run --release -- --urgency critical
+//! cargo run --release -- --category success
+//!
+//! # one icon per urgency/category variant instead of a single image:
+//! cargo run --release -- --icon-set
+//!
+//! # animated icon instead of a single image:
+//! cargo run --release -- --frames 8 --fps 12 --anim-format gif
+//! cargo run --release -- --frames 8 --anim-format apng
 //! ```
 
+use icon_gen::anim;
+use icon_gen::clipboard;
+use icon_gen::hints::{NotificationHints, Urgency};
+use icon_gen::pixelate::{pixelate as apply_pixelate, PixelateOptions};
+use icon_gen::preview;
+use icon_gen::report;
 use image::imageops::FilterType;
-use image::ImageFormat;
+use image::{ImageFormat, RgbaImage};
 use rust_genai::{Client, InteractionResponseExt, InteractionStatus};
 use std::env;
 use std::io::Cursor;
@@ -19,7 +43,130 @@ use std::path::PathBuf;
 
 const DEFAULT_PROMPT: &str = r#"Create a pixel art style icon (32x32 pixels scaled up) of a cute white and orange Birman cat with bright blue eyes, playfully batting at a colorful ball of yarn. The yarn ball should have rainbow colors (red, orange, yellow, green, blue, purple). The cat should have the characteristic Birman coloring: creamy white body with orange/seal points on the face, ears, and paws. The style should be clean pixel art suitable for a macOS notification icon. Transparent background. The cat should look happy and playful."#;
 
-fn save_image(bytes: &[u8], output_dir: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+/// Side length (in pixels) of each frame in an animated icon.
+const ANIM_FRAME_SIZE: u32 = 512;
+
+/// Parsed command-line options: the leftover args join into the prompt.
+struct Args {
+    prompt: String,
+    pixelate: Option<PixelateOptions>,
+    preview: bool,
+    frames: Option<u32>,
+    anim_format: AnimFormat,
+    fps: u32,
+    icon_set: bool,
+    info: bool,
+    copy: bool,
+    hints: NotificationHints,
+}
+
+#[derive(Clone, Copy)]
+enum AnimFormat {
+    Gif,
+    Apng,
+}
+
+/// Flags controlling how `save_image` writes and presents its output.
+#[derive(Clone, Copy)]
+struct SaveOptions<'a> {
+    pixelate: Option<&'a PixelateOptions>,
+    preview: bool,
+    info: bool,
+    copy: bool,
+}
+
+/// Flags controlling a `--frames` animated-icon run.
+struct AnimOptions {
+    count: u32,
+    fps: u32,
+    format: AnimFormat,
+}
+
+fn parse_args() -> Args {
+    let mut opts = PixelateOptions::default();
+    let mut no_pixelate = false;
+    let mut preview = false;
+    let mut frames = None;
+    let mut anim_format = AnimFormat::Gif;
+    let mut fps = 12;
+    let mut icon_set = false;
+    let mut info = false;
+    let mut copy = false;
+    let mut urgency = Urgency::Normal;
+    let mut category = String::new();
+    let mut prompt_parts = Vec::new();
+
+    let mut args = env::args().skip(1).peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--palette-size" => {
+                if let Some(v) = args.next() {
+                    opts.palette_size = v.parse().expect("--palette-size expects an integer");
+                }
+            }
+            "--grid" => {
+                if let Some(v) = args.next() {
+                    opts.grid = v.parse().expect("--grid expects an integer");
+                }
+            }
+            "--no-pixelate" => no_pixelate = true,
+            "--preview" => preview = true,
+            "--icon-set" => icon_set = true,
+            "--info" => info = true,
+            "--copy" => copy = true,
+            "--urgency" => {
+                urgency = match args.next().as_deref() {
+                    Some("low") => Urgency::Low,
+                    Some("normal") => Urgency::Normal,
+                    Some("critical") => Urgency::Critical,
+                    other => panic!("--urgency expects \"low\", \"normal\", or \"critical\", got: {:?}", other),
+                };
+            }
+            "--category" => {
+                if let Some(v) = args.next() {
+                    category = v;
+                }
+            }
+            "--frames" => {
+                if let Some(v) = args.next() {
+                    frames = Some(v.parse().expect("--frames expects an integer"));
+                }
+            }
+            "--fps" => {
+                if let Some(v) = args.next() {
+                    fps = v.parse().expect("--fps expects an integer");
+                }
+            }
+            "--anim-format" => {
+                anim_format = match args.next().as_deref() {
+                    Some("gif") => AnimFormat::Gif,
+                    Some("apng") => AnimFormat::Apng,
+                    other => panic!("--anim-format expects \"gif\" or \"apng\", got: {:?}", other),
+                };
+            }
+            other => prompt_parts.push(other.to_string()),
+        }
+    }
+
+    Args {
+        prompt: if prompt_parts.is_empty() {
+            DEFAULT_PROMPT.to_string()
+        } else {
+            prompt_parts.join(" ")
+        },
+        pixelate: if no_pixelate { None } else { Some(opts) },
+        preview,
+        frames,
+        anim_format,
+        fps,
+        icon_set,
+        info,
+        copy,
+        hints: NotificationHints::new(urgency, category),
+    }
+}
+
+fn save_image(bytes: &[u8], output_dir: &PathBuf, name: &str, opts: SaveOptions) -> Result<(), Box<dyn std::error::Error>> {
     // Load image from bytes (already decoded)
     let img = image::load_from_memory(bytes)?;
     println!("Original size: {}x{}", img.width(), img.height());
@@ -31,28 +178,166 @@ fn save_image(bytes: &[u8], output_dir: &PathBuf) -> Result<(), Box<dyn std::err
     let top = (h - size) / 2;
     let img_square = img.crop_imm(left, top, size, size);
 
+    if opts.info {
+        report::print_palette_report(&img_square.to_rgba8());
+    }
+
     // Create output directory
     std::fs::create_dir_all(output_dir)?;
 
     // Save at different sizes
     for target_size in [512u32, 1024u32] {
-        let resized = img_square.resize(target_size, target_size, FilterType::Lanczos3);
-        let path = output_dir.join(format!("icon-{}.png", target_size));
+        let resized = match opts.pixelate {
+            Some(pixelate_opts) => apply_pixelate(&img_square, pixelate_opts, target_size, target_size),
+            None => img_square.resize(target_size, target_size, FilterType::Lanczos3),
+        };
+        let path = output_dir.join(format!("{}-{}.png", name, target_size));
 
         let mut buf = Cursor::new(Vec::new());
         resized.write_to(&mut buf, ImageFormat::Png)?;
-        std::fs::write(&path, buf.into_inner())?;
+        let png_bytes = buf.into_inner();
+        std::fs::write(&path, &png_bytes)?;
 
         println!("Saved: {}", path.display());
+
+        if opts.preview && target_size == 512 && !preview::preview_png(&png_bytes) {
+            println!("(terminal preview unsupported, open {} to view)", path.display());
+        }
     }
 
     // Save original cropped version
-    let path = output_dir.join("icon.png");
+    let icon = match opts.pixelate {
+        Some(pixelate_opts) => apply_pixelate(&img_square, pixelate_opts, size, size),
+        None => img_square,
+    };
+    let path = output_dir.join(format!("{}.png", name));
     let mut buf = Cursor::new(Vec::new());
-    img_square.write_to(&mut buf, ImageFormat::Png)?;
+    icon.write_to(&mut buf, ImageFormat::Png)?;
     std::fs::write(&path, buf.into_inner())?;
     println!("Saved: {}", path.display());
 
+    if opts.copy {
+        clipboard::copy_to_clipboard(&icon.to_rgba8())?;
+        println!("Copied {} to clipboard", path.display());
+    }
+
+    Ok(())
+}
+
+/// Per-variant prompt suffixes for the named icon set; `--urgency`/`--category`
+/// pick one of these by name via `NotificationHints::icon_variant`.
+const ICON_SET_VARIANTS: [(&str, &str); 3] = [
+    ("icon-normal", ""),
+    (
+        "icon-critical",
+        " The cat should look alarmed, ears back, with a small red warning glyph overlaid, to signal a critical/urgent notification.",
+    ),
+    (
+        "icon-success",
+        " The cat should look triumphant and pleased, with a small green checkmark sparkle overlaid, to signal a successful/completed notification.",
+    ),
+];
+
+/// Generate and save the `icon-normal`/`icon-critical`/`icon-success` set
+/// consumed by hint-driven icon selection.
+async fn generate_icon_set(
+    client: &Client,
+    model: &str,
+    base_prompt: &str,
+    opts: SaveOptions<'_>,
+    output_dir: &PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (name, suffix) in ICON_SET_VARIANTS {
+        let prompt = format!("{base_prompt}{suffix}");
+        println!("Generating {}...", name);
+        let bytes = generate_one(client, model, &prompt).await?;
+        save_image(&bytes, output_dir, name, opts)?;
+    }
+    Ok(())
+}
+
+fn assets_dir() -> PathBuf {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest_dir)
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("assets")
+}
+
+/// Request a single image from Gemini and return the raw bytes.
+async fn generate_one(client: &Client, model: &str, prompt: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let response = client
+        .interaction()
+        .with_model(model)
+        .with_text(prompt)
+        .with_image_output()
+        .create()
+        .await?;
+
+    if response.status != InteractionStatus::Completed {
+        return Err(format!("Interaction failed: {:?}", response.status).into());
+    }
+
+    Ok(response.first_image_bytes()?.ok_or("No image in response")?)
+}
+
+/// Center-crop `bytes` to a square and resize/pixelate it to `size`x`size`.
+fn square_frame(bytes: &[u8], size: u32, pixelate_opts: Option<&PixelateOptions>) -> Result<RgbaImage, Box<dyn std::error::Error>> {
+    let img = image::load_from_memory(bytes)?;
+    let (w, h) = (img.width(), img.height());
+    let crop_size = w.min(h);
+    let left = (w - crop_size) / 2;
+    let top = (h - crop_size) / 2;
+    let square = img.crop_imm(left, top, crop_size, crop_size);
+
+    let resized = match pixelate_opts {
+        Some(opts) => apply_pixelate(&square, opts, size, size),
+        None => square.resize(size, size, FilterType::Lanczos3),
+    };
+    Ok(resized.to_rgba8())
+}
+
+/// Generate `count` frames with incrementally varied prompts and assemble
+/// them into a looping GIF or APNG of the cat batting the yarn.
+async fn generate_animation(
+    client: &Client,
+    model: &str,
+    base_prompt: &str,
+    anim_opts: &AnimOptions,
+    pixelate_opts: Option<&PixelateOptions>,
+    output_dir: &PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let count = anim_opts.count;
+    if count == 0 {
+        return Err("--frames must be at least 1".into());
+    }
+    let mut frames = Vec::with_capacity(count as usize);
+    for i in 1..=count {
+        let prompt = format!("{base_prompt}\n\nThis is frame {i} of {count} in a seamless loop animation of the cat batting the yarn; vary the pose slightly from the previous frame (paw raised a bit more, yarn ball nudged a bit further) while keeping the character design, colors, and framing identical.");
+        println!("Generating frame {}/{}...", i, count);
+        let bytes = generate_one(client, model, &prompt).await?;
+        frames.push(square_frame(&bytes, ANIM_FRAME_SIZE, pixelate_opts)?);
+    }
+
+    std::fs::create_dir_all(output_dir)?;
+    let path = match anim_opts.format {
+        AnimFormat::Gif => {
+            let path = output_dir.join("icon-anim.gif");
+            let delay_ms = (1000 / anim_opts.fps.max(1)) as u16;
+            let palette_size = pixelate_opts.map(|o| o.palette_size).unwrap_or(PixelateOptions::default().palette_size);
+            anim::encode_gif(&frames, delay_ms, palette_size, &path)?;
+            path
+        }
+        AnimFormat::Apng => {
+            let path = output_dir.join("icon-anim.png");
+            anim::encode_apng(&frames, anim_opts.fps, &path)?;
+            path
+        }
+    };
+    println!("Saved: {}", path.display());
+
     Ok(())
 }
 
@@ -62,18 +347,42 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let client = Client::builder(api_key).build();
 
-    // Get prompt from args or use default
-    let args: Vec<String> = env::args().collect();
-    let prompt = if args.len() > 1 {
-        args[1..].join(" ")
-    } else {
-        DEFAULT_PROMPT.to_string()
-    };
+    let Args { prompt, pixelate, preview, frames, anim_format, fps, icon_set, info, copy, hints } = parse_args();
 
     println!("=== EVENT BUS ICON GENERATION ===\n");
     println!("Prompt: {}\n", &prompt[..100.min(prompt.len())]);
 
     let model = "gemini-3-pro-image-preview";
+    let output_dir = assets_dir();
+
+    let save_opts = SaveOptions {
+        pixelate: pixelate.as_ref(),
+        preview,
+        info,
+        copy,
+    };
+
+    if icon_set {
+        generate_icon_set(&client, model, &prompt, save_opts, &output_dir).await?;
+        return Ok(());
+    }
+
+    if let Some(count) = frames {
+        let anim_opts = AnimOptions { count, fps, format: anim_format };
+        generate_animation(&client, model, &prompt, &anim_opts, pixelate.as_ref(), &output_dir).await?;
+        return Ok(());
+    }
+
+    // Hint-driven variant: a non-default urgency/category picks the matching
+    // `ICON_SET_VARIANTS` entry (prompt suffix and filename) instead of
+    // overwriting the plain `icon*.png` files.
+    let variant = hints.icon_variant();
+    let name = if variant == "normal" { "icon".to_string() } else { format!("icon-{variant}") };
+    let suffix = ICON_SET_VARIANTS
+        .iter()
+        .find(|(set_name, _)| *set_name == format!("icon-{variant}"))
+        .map_or("", |(_, suffix)| *suffix);
+    let prompt = format!("{prompt}{suffix}");
 
     let result = client
         .interaction()
@@ -88,25 +397,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Status: {:?}", response.status);
 
             if response.status == InteractionStatus::Completed {
-                // Get assets directory (relative to this crate)
-                let manifest_dir = env!("CARGO_MANIFEST_DIR");
-                let output_dir = PathBuf::from(manifest_dir)
-                    .parent()
-                    .unwrap()
-                    .parent()
-                    .unwrap()
-                    .join("assets");
-
                 // Use new DX helper - no manual base64 decoding needed!
                 let bytes = response
                     .first_image_bytes()?
                     .ok_or("No image in response")?;
 
-                save_image(&bytes, &output_dir)?;
+                println!("Notification hints: urgency={:?}, category={:?} -> {}", hints.urgency, hints.category, name);
+
+                save_image(&bytes, &output_dir, &name, save_opts)?;
                 println!("\nIcons saved to: {}", output_dir.display());
                 println!(
-                    "\nTo use:\n  EVENT_BUS_ICON={}/icon-512.png event-bus",
-                    output_dir.display()
+                    "\nTo use:\n  EVENT_BUS_ICON={}/{}-512.png event-bus",
+                    output_dir.display(),
+                    name
                 );
             }
 
@@ -0,0 +1,315 @@
+//! Median-cut color quantization for genuine pixel-art icons.
+//!
+//! Gemini's "pixel art" output is smooth and anti-aliased. This module turns
+//! it into the real thing: downscale to a small grid with area averaging,
+//! collapse the colors to a fixed palette via median-cut, then upscale back
+//! with nearest-neighbor so the pixel edges stay hard.
+
+use image::{DynamicImage, Rgba, RgbaImage};
+
+/// Largest palette `shared_palette` will ever produce. Indexed-color encoders
+/// (GIF) reserve one extra index past the palette for the transparent color,
+/// and that index is stored in a `u8`, so the palette itself must leave room
+/// for it or `palette.len() as u8` wraps to 0 and aliases the transparent
+/// slot onto real palette entry 0.
+const MAX_INDEXED_PALETTE_SIZE: usize = 255;
+
+/// Options controlling the pixel-art quantization pass.
+#[derive(Clone, Copy, Debug)]
+pub struct PixelateOptions {
+    /// Side length of the low-res grid the image is downsampled to before quantizing.
+    pub grid: u32,
+    /// Number of colors in the output palette.
+    pub palette_size: usize,
+}
+
+impl Default for PixelateOptions {
+    fn default() -> Self {
+        Self {
+            grid: 32,
+            palette_size: 24,
+        }
+    }
+}
+
+/// Downscale `img` to `opts.grid`x`opts.grid` via area averaging, quantize to
+/// `opts.palette_size` colors via median-cut, then upscale to `(out_w, out_h)`
+/// with nearest-neighbor so the result reads as real pixel art.
+pub fn pixelate(img: &DynamicImage, opts: &PixelateOptions, out_w: u32, out_h: u32) -> DynamicImage {
+    let small = area_average_downscale(img, opts.grid, opts.grid);
+    let palette = median_cut_palette(&small, opts.palette_size);
+    let quantized = apply_palette(&small, &palette);
+
+    DynamicImage::ImageRgba8(quantized).resize_exact(out_w, out_h, image::imageops::FilterType::Nearest)
+}
+
+/// Downscale `img` to `grid_w`x`grid_h` by averaging every source pixel that
+/// falls within each destination cell, alpha included.
+fn area_average_downscale(img: &DynamicImage, grid_w: u32, grid_h: u32) -> RgbaImage {
+    let (src_w, src_h) = (img.width(), img.height());
+    let rgba = img.to_rgba8();
+    let mut out = RgbaImage::new(grid_w, grid_h);
+
+    for gy in 0..grid_h {
+        let y0 = gy * src_h / grid_h;
+        let y1 = ((gy + 1) * src_h / grid_h).max(y0 + 1).min(src_h);
+        for gx in 0..grid_w {
+            let x0 = gx * src_w / grid_w;
+            let x1 = ((gx + 1) * src_w / grid_w).max(x0 + 1).min(src_w);
+
+            let mut sum = [0u64; 4];
+            let mut count = 0u64;
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let Rgba(px) = *rgba.get_pixel(x, y);
+                    for c in 0..4 {
+                        sum[c] += px[c] as u64;
+                    }
+                    count += 1;
+                }
+            }
+
+            let avg = sum.map(|s| (s / count.max(1)) as u8);
+            out.put_pixel(gx, gy, Rgba(avg));
+        }
+    }
+
+    out
+}
+
+/// Recursively split the bounding box of `pixels` along its longest-range
+/// channel until `target_size` boxes remain, then average each box to a
+/// single palette entry.
+fn median_cut_palette(img: &RgbaImage, target_size: usize) -> Vec<[u8; 4]> {
+    let pixels: Vec<[u8; 4]> = img.pixels().map(|Rgba(p)| *p).collect();
+    median_cut(pixels, target_size)
+}
+
+/// Build one shared palette from every pixel across `images`, so a sequence
+/// of animation frames quantizes to the same colors instead of each frame
+/// picking its own (which flickers between frames).
+///
+/// `target_size` is clamped to [`MAX_INDEXED_PALETTE_SIZE`] so the result is
+/// always safe to feed through `apply_shared_palette_indexed` into an
+/// indexed-color encoder.
+pub fn shared_palette(images: &[RgbaImage], target_size: usize) -> Vec<[u8; 4]> {
+    let pixels: Vec<[u8; 4]> = images.iter().flat_map(|img| img.pixels().map(|Rgba(p)| *p)).collect();
+    median_cut(pixels, target_size.min(MAX_INDEXED_PALETTE_SIZE))
+}
+
+fn median_cut(pixels: Vec<[u8; 4]>, target_size: usize) -> Vec<[u8; 4]> {
+    if pixels.is_empty() {
+        return vec![[0, 0, 0, 0]];
+    }
+
+    let mut boxes = vec![pixels];
+    while boxes.len() < target_size {
+        let Some((split_idx, channel)) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.len() > 1)
+            .map(|(i, b)| (i, widest_channel(b)))
+            .max_by_key(|(i, ch)| channel_range(&boxes[*i], *ch))
+        else {
+            break;
+        };
+
+        let removed = boxes.swap_remove(split_idx);
+        let (lo, hi) = split_box(removed, channel);
+        boxes.push(lo);
+        boxes.push(hi);
+    }
+
+    boxes.iter().map(|b| average_color(b)).collect()
+}
+
+/// Channel (0=R, 1=G, 2=B) with the largest value range in `pixels`.
+fn widest_channel(pixels: &[[u8; 4]]) -> usize {
+    (0..3).max_by_key(|&ch| channel_range(pixels, ch)).unwrap_or(0)
+}
+
+fn channel_range(pixels: &[[u8; 4]], channel: usize) -> u32 {
+    let (min, max) = pixels.iter().fold((255u8, 0u8), |(min, max), p| {
+        (min.min(p[channel]), max.max(p[channel]))
+    });
+    (max - min) as u32
+}
+
+/// Split `pixels` in half by median value along `channel`.
+fn split_box(mut pixels: Vec<[u8; 4]>, channel: usize) -> (Vec<[u8; 4]>, Vec<[u8; 4]>) {
+    pixels.sort_unstable_by_key(|p| p[channel]);
+    let mid = pixels.len() / 2;
+    let hi = pixels.split_off(mid);
+    (pixels, hi)
+}
+
+fn average_color(pixels: &[[u8; 4]]) -> [u8; 4] {
+    let mut sum = [0u64; 4];
+    for p in pixels {
+        for c in 0..4 {
+            sum[c] += p[c] as u64;
+        }
+    }
+    let n = pixels.len().max(1) as u64;
+    sum.map(|s| (s / n) as u8)
+}
+
+/// Map every pixel in `img` to its nearest palette entry by squared-Euclidean
+/// RGB distance, preserving alpha by thresholding it at 128 (fully opaque or
+/// fully transparent, no semi-transparent pixel-art edges).
+fn apply_palette(img: &RgbaImage, palette: &[[u8; 4]]) -> RgbaImage {
+    let mut out = RgbaImage::new(img.width(), img.height());
+    for (x, y, Rgba(px)) in img.enumerate_pixels() {
+        let nearest = palette
+            .iter()
+            .min_by_key(|entry| rgb_distance_sq(entry, px))
+            .copied()
+            .unwrap_or([0, 0, 0, 0]);
+        let alpha = if px[3] >= 128 { 255 } else { 0 };
+        out.put_pixel(x, y, Rgba([nearest[0], nearest[1], nearest[2], alpha]));
+    }
+    out
+}
+
+/// Top-`top_k` dominant colors in `img`, each as an `(RGB hex triple,
+/// percentage of opaque pixels)` pair sorted by coverage descending.
+///
+/// Built from the same median-cut histogram used for the pixel-art and
+/// shared-animation-palette passes, but over the image's opaque pixels only
+/// (so a transparent background doesn't drown out the actual artwork).
+pub fn dominant_colors(img: &RgbaImage, palette_size: usize, top_k: usize) -> Vec<([u8; 3], f32)> {
+    let opaque: Vec<[u8; 4]> = img.pixels().map(|Rgba(p)| *p).filter(|p| p[3] >= 128).collect();
+    if opaque.is_empty() {
+        return Vec::new();
+    }
+
+    let palette = median_cut(opaque.clone(), palette_size);
+    let mut counts = vec![0u64; palette.len()];
+    for px in &opaque {
+        let idx = palette
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| rgb_distance_sq(entry, px))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        counts[idx] += 1;
+    }
+
+    let total = opaque.len() as f32;
+    let mut entries: Vec<([u8; 3], f32)> = palette
+        .iter()
+        .zip(counts)
+        .map(|(p, count)| ([p[0], p[1], p[2]], count as f32 / total * 100.0))
+        .collect();
+    entries.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    entries.truncate(top_k);
+    entries
+}
+
+/// Map every pixel in `img` to an index into `palette`, reserving one extra
+/// index (`palette.len()`) for fully-transparent pixels (alpha below 128) so
+/// an indexed-color encoder like GIF can mark it as the transparent color.
+/// Returns the per-pixel indices and that reserved transparent index.
+pub fn apply_shared_palette_indexed(img: &RgbaImage, palette: &[[u8; 4]]) -> (Vec<u8>, u8) {
+    let transparent_index = palette.len() as u8;
+    let indices = img
+        .pixels()
+        .map(|Rgba(px)| {
+            if px[3] < 128 {
+                transparent_index
+            } else {
+                palette
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, entry)| rgb_distance_sq(entry, px))
+                    .map(|(i, _)| i as u8)
+                    .unwrap_or(0)
+            }
+        })
+        .collect();
+    (indices, transparent_index)
+}
+
+fn rgb_distance_sq(a: &[u8; 4], b: &[u8; 4]) -> u32 {
+    (0..3)
+        .map(|c| {
+            let d = a[c] as i32 - b[c] as i32;
+            (d * d) as u32
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(color: [u8; 4], w: u32, h: u32) -> RgbaImage {
+        RgbaImage::from_fn(w, h, |_, _| Rgba(color))
+    }
+
+    #[test]
+    fn median_cut_never_exceeds_requested_size() {
+        let pixels = vec![[0, 0, 0, 255], [255, 255, 255, 255], [128, 64, 200, 255], [10, 200, 30, 255]];
+        assert_eq!(median_cut(pixels.clone(), 2).len(), 2);
+        // Fewer pixels than requested just yields fewer boxes.
+        assert!(median_cut(pixels, 8).len() <= 4);
+    }
+
+    #[test]
+    fn median_cut_of_empty_pixels_returns_one_entry() {
+        assert_eq!(median_cut(Vec::new(), 4), vec![[0, 0, 0, 0]]);
+    }
+
+    #[test]
+    fn apply_palette_snaps_to_nearest_entry() {
+        let img = solid([250, 5, 5, 255], 2, 2);
+        let palette = vec![[255, 0, 0, 255], [0, 255, 0, 255]];
+        let out = apply_palette(&img, &palette);
+        for (_, _, Rgba(px)) in out.enumerate_pixels() {
+            assert_eq!([px[0], px[1], px[2]], [255, 0, 0]);
+        }
+    }
+
+    #[test]
+    fn apply_palette_thresholds_alpha() {
+        let img = solid([0, 0, 0, 64], 1, 1);
+        let palette = vec![[0, 0, 0, 255]];
+        let out = apply_palette(&img, &palette);
+        assert_eq!(out.get_pixel(0, 0).0[3], 0);
+    }
+
+    #[test]
+    fn dominant_colors_ignores_transparent_pixels() {
+        let mut img = solid([255, 0, 0, 255], 2, 2);
+        img.put_pixel(0, 0, Rgba([0, 0, 0, 0]));
+        let colors = dominant_colors(&img, 4, 4);
+        assert!(colors.iter().all(|(rgb, _)| *rgb == [255, 0, 0]));
+        let total_coverage: f32 = colors.iter().map(|(_, pct)| pct).sum();
+        assert!((total_coverage - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn dominant_colors_of_fully_transparent_image_is_empty() {
+        let img = solid([0, 0, 0, 0], 2, 2);
+        assert!(dominant_colors(&img, 4, 4).is_empty());
+    }
+
+    #[test]
+    fn shared_palette_clamps_to_indexed_limit() {
+        let images = vec![solid([1, 2, 3, 255], 4, 4)];
+        let palette = shared_palette(&images, 1000);
+        assert!(palette.len() <= MAX_INDEXED_PALETTE_SIZE);
+    }
+
+    #[test]
+    fn apply_shared_palette_indexed_transparent_index_never_wraps() {
+        // A 255-entry palette is the largest `shared_palette` will ever return,
+        // so the reserved transparent index (`palette.len()`) must still fit
+        // in a u8 without wrapping to 0.
+        let palette: Vec<[u8; 4]> = (0..255u32).map(|i| [i as u8, 0, 0, 255]).collect();
+        let img = solid([0, 0, 0, 0], 1, 1);
+        let (_, transparent_index) = apply_shared_palette_indexed(&img, &palette);
+        assert_eq!(transparent_index, 255);
+        assert_ne!(transparent_index, 0);
+    }
+}
@@ -0,0 +1,71 @@
+//! Inline terminal image preview via the kitty graphics protocol, with an
+//! iTerm2 inline-image fallback.
+//!
+//! Lets `smart-crop` and the icon generator show the result directly in the
+//! terminal instead of only printing a path for the user to open elsewhere.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::env;
+
+/// Max size of a single base64 chunk in a kitty graphics escape sequence.
+const CHUNK_SIZE: usize = 4096;
+
+/// Terminal graphics protocol the current session supports, if any.
+#[derive(Debug, PartialEq, Eq)]
+enum Support {
+    Kitty,
+    Iterm2,
+    None,
+}
+
+fn detect_support() -> Support {
+    if env::var("KITTY_WINDOW_ID").is_ok() {
+        return Support::Kitty;
+    }
+    if env::var("TERM").unwrap_or_default().contains("kitty") {
+        return Support::Kitty;
+    }
+    if env::var("TERM_PROGRAM").map(|p| p == "iTerm.app").unwrap_or(false) {
+        return Support::Iterm2;
+    }
+    Support::None
+}
+
+/// Print `png_bytes` inline in the terminal if a supported graphics protocol
+/// is detected. Returns `true` if a preview was emitted, `false` if the
+/// terminal is unsupported and the caller should just print the path.
+pub fn preview_png(png_bytes: &[u8]) -> bool {
+    match detect_support() {
+        Support::Kitty => {
+            print_kitty(png_bytes);
+            true
+        }
+        Support::Iterm2 => {
+            print_iterm2(png_bytes);
+            true
+        }
+        Support::None => false,
+    }
+}
+
+fn print_kitty(png_bytes: &[u8]) {
+    let encoded = STANDARD.encode(png_bytes);
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(CHUNK_SIZE).collect();
+    let last = chunks.len().saturating_sub(1);
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i == last { 0 } else { 1 };
+        let payload = std::str::from_utf8(chunk).expect("base64 output is always valid UTF-8");
+        if i == 0 {
+            print!("\x1b_Gf=100,a=T,m={};{}\x1b\\", more, payload);
+        } else {
+            print!("\x1b_Gm={};{}\x1b\\", more, payload);
+        }
+    }
+    println!();
+}
+
+fn print_iterm2(png_bytes: &[u8]) {
+    let encoded = STANDARD.encode(png_bytes);
+    println!("\x1b]1337;File=inline=1:{}\x07", encoded);
+}
@@ -0,0 +1,20 @@
+//! Print a dominant-color palette report for a generated or cropped icon.
+
+use crate::pixelate::dominant_colors;
+use image::RgbaImage;
+
+/// Palette size fed into the median-cut histogram before picking the top colors.
+const REPORT_PALETTE_SIZE: usize = 32;
+
+/// Number of dominant colors to report.
+const TOP_K: usize = 8;
+
+/// Print the top dominant colors in `img` (post-crop, pre-pixelation) as hex
+/// codes with their percentage coverage, sorted by coverage descending.
+pub fn print_palette_report(img: &RgbaImage) {
+    let colors = dominant_colors(img, REPORT_PALETTE_SIZE, TOP_K);
+    println!("\nDominant colors:");
+    for ([r, g, b], coverage) in colors {
+        println!("  #{:02x}{:02x}{:02x}  {:.1}%", r, g, b, coverage);
+    }
+}